@@ -225,6 +225,10 @@ def insert_file(rev: str, filename: str) -> str:
         return "".join(data)
 
 
+def has_data(rev: str, bench: str) -> bool:
+    return (DATADIR / rev / bench).exists()
+
+
 def regex_replace(data: str, regex: str, subs: str) -> str:
     reg = re.compile(regex, flags=re.MULTILINE)
     return reg.sub(subs, data)
@@ -239,6 +243,7 @@ def generate_report(templates: list[str]) -> None:
     env.filters.update(regex_replace=regex_replace)
     env.globals.update(
         insert_file=insert_file,
+        has_data=has_data,
         runs=list(reversed(list(zip(RUNS, tmpls)))),
         benches=BENCHES,
     )
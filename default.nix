@@ -1,6 +1,7 @@
 {
   lib,
   rustPlatform,
+  features ? [],
 }: let
   cargoToml = (lib.importTOML ./Cargo.toml).package;
 in
@@ -12,5 +13,7 @@ in
       path = ./.;
     };
     cargoLock.lockFile = ./Cargo.lock;
+    buildFeatures = features;
+    checkFeatures = features;
     useNextest = true;
   }
@@ -31,6 +31,8 @@
       pkgs,
       system,
     }: {
+      # The default package does not build the optional `rayon` feature
+      sudoku-solver-rayon = pkgs.callPackage ./default.nix {features = ["rayon"];};
       pre-commit-check = pre-commit-hooks.lib.${system}.run {
         src = builtins.path {
           path = ./.;
@@ -0,0 +1,87 @@
+//! Errors returned by the solver library
+use std::fmt;
+
+use crate::solver::PartialSolution;
+
+/// The [`Solver`](crate::solver::Solver) tried every possible value and found no solution
+///
+/// Contains the [`Sudoku`](crate::solver::Sudoku) the solver gave up on, with the candidates left
+/// for each cell. Exhaustive solvers like [`IterativeDFS`](crate::solver::IterativeDFS) undo every
+/// guess before giving up, so this is just the givens with their candidates.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ExhaustedAllPossibilities(pub PartialSolution);
+
+impl fmt::Display for ExhaustedAllPossibilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "exhausted all possibilities without finding a solution")?;
+        match self.0.dead_ends().count() {
+            0 => Ok(()),
+            1 => write!(f, " (1 cell has no candidates)"),
+            n => write!(f, " ({n} cells have no candidates)"),
+        }
+    }
+}
+
+impl std::error::Error for ExhaustedAllPossibilities {}
+
+/// Tried to read a value from an empty cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EmptySudokuCell;
+
+impl fmt::Display for EmptySudokuCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the sudoku cell is empty")
+    }
+}
+
+impl std::error::Error for EmptySudokuCell {}
+
+/// Tried to convert a [`Sudoku`](crate::solver::Sudoku) that is not solved into a
+/// [`SolvedSudoku`](crate::solver::SolvedSudoku)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UnsolvedSudoku;
+
+impl fmt::Display for UnsolvedSudoku {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the sudoku is not solved")
+    }
+}
+
+impl std::error::Error for UnsolvedSudoku {}
+
+/// Failed to parse a [`Sudoku`](crate::solver::Sudoku) from a line of ascii characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseSudokuError {
+    /// The line is not 81 characters long
+    InvalidLength(usize),
+    /// A character is neither `.` nor a digit in `1..=9`
+    InvalidChar {
+        /// Index of the character in the line
+        ix: usize,
+        /// The offending character
+        byte: u8,
+    },
+}
+
+impl fmt::Display for ParseSudokuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(len) => {
+                write!(f, "expected 81 cells, found {len}")
+            }
+            Self::InvalidChar { ix, byte } => {
+                write!(
+                    f,
+                    "invalid character '{}' at cell {ix}, expected '.' or a digit in 1-9",
+                    byte.escape_ascii()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseSudokuError {}
@@ -0,0 +1,310 @@
+//! Configurable text output for [`Sudoku`]s
+use std::fmt;
+
+use crate::solver::Sudoku;
+
+/// Characters used to draw the box borders of a [`SudokuFormatter`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BorderStyle {
+    /// `+`, `-` and `|`
+    #[default]
+    Ascii,
+    /// Unicode box drawing characters
+    Unicode,
+}
+
+impl BorderStyle {
+    fn horizontal(self) -> char {
+        match self {
+            Self::Ascii => '-',
+            Self::Unicode => '─',
+        }
+    }
+
+    fn vertical(self) -> char {
+        match self {
+            Self::Ascii => '|',
+            Self::Unicode => '│',
+        }
+    }
+
+    /// Junctions (left, inner and right) of the horizontal border at `row` (0 to 3)
+    fn junctions(self, row: usize) -> [char; 3] {
+        match (self, row) {
+            (Self::Ascii, _) => ['+'; 3],
+            (Self::Unicode, 0) => ['┌', '┬', '┐'],
+            (Self::Unicode, 3) => ['└', '┴', '┘'],
+            (Self::Unicode, _) => ['├', '┼', '┤'],
+        }
+    }
+}
+
+/// Formats a [`Sudoku`] as text
+///
+/// Start from [`line`](SudokuFormatter::line) (the format accepted by [`Sudoku::from_line`]) or
+/// [`grid`](SudokuFormatter::grid) (a pretty printed grid) and customize it with the builder
+/// methods:
+///
+/// ```
+/// # use libsolver::{formatter::{BorderStyle, SudokuFormatter}, solver::Sudoku};
+/// let sudoku = Sudoku::from_line(
+///     b".......1.4.........2...........5.4.7..8...3....1.9....3..4..2...5.1........8.6...",
+/// );
+/// let formatter = SudokuFormatter::grid()
+///     .empty('.')
+///     .borders(Some(BorderStyle::Unicode));
+/// println!("{}", formatter.display(&sudoku));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SudokuFormatter {
+    empty: char,
+    cell_separator: Option<char>,
+    row_separator: Option<char>,
+    borders: Option<BorderStyle>,
+    highlight: [[bool; 9]; 9],
+}
+
+impl Default for SudokuFormatter {
+    fn default() -> Self {
+        Self::line()
+    }
+}
+
+impl SudokuFormatter {
+    /// A single line of 81 characters with empty cells shown as `.`
+    pub fn line() -> Self {
+        Self {
+            empty: '.',
+            cell_separator: None,
+            row_separator: None,
+            borders: None,
+            highlight: [[false; 9]; 9],
+        }
+    }
+
+    /// A grid with ASCII box borders and empty cells shown as ` `
+    pub fn grid() -> Self {
+        Self {
+            empty: ' ',
+            cell_separator: Some(' '),
+            row_separator: Some('\n'),
+            borders: Some(BorderStyle::Ascii),
+            highlight: [[false; 9]; 9],
+        }
+    }
+
+    /// Character used for empty cells
+    #[must_use]
+    pub fn empty(mut self, empty: char) -> Self {
+        self.empty = empty;
+        self
+    }
+
+    /// Character placed between the cells of a row
+    ///
+    /// When drawing borders it is also used as padding between the borders and the cells.
+    #[must_use]
+    pub fn cell_separator(mut self, separator: Option<char>) -> Self {
+        self.cell_separator = separator;
+        self
+    }
+
+    /// Character placed between rows
+    ///
+    /// Ignored when drawing borders, as the borders always go on their own line.
+    #[must_use]
+    pub fn row_separator(mut self, separator: Option<char>) -> Self {
+        self.row_separator = separator;
+        self
+    }
+
+    /// Draw borders around the boxes (or not)
+    #[must_use]
+    pub fn borders(mut self, borders: Option<BorderStyle>) -> Self {
+        self.borders = borders;
+        self
+    }
+
+    /// Highlight the cell at `ix` (using ANSI escape codes)
+    #[must_use]
+    pub fn highlight(mut self, ix: impl Into<[usize; 2]>) -> Self {
+        let [x, y] = ix.into();
+        self.highlight[y][x] = true;
+        self
+    }
+
+    /// Highlight the filled cells of `sudoku`, e.g. to tell the givens apart in a solution
+    #[must_use]
+    pub fn highlight_filled(mut self, sudoku: &Sudoku) -> Self {
+        for ([x, y], cell) in sudoku.indexed_values() {
+            self.highlight[y][x] |= cell.is_filled();
+        }
+        self
+    }
+
+    /// A [`Display`](fmt::Display)able view of `sudoku` using this format
+    pub fn display<'a>(&'a self, sudoku: &'a Sudoku) -> FormattedSudoku<'a> {
+        FormattedSudoku {
+            formatter: self,
+            sudoku,
+        }
+    }
+
+    /// Write `sudoku` to `f` using this format
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if writing to `f` fails.
+    pub fn write(&self, f: &mut impl fmt::Write, sudoku: &Sudoku) -> fmt::Result {
+        let Some(style) = self.borders else {
+            for y in 0..9 {
+                if y > 0 {
+                    self.write_sep(f, self.row_separator)?;
+                }
+                for x in 0..9 {
+                    if x > 0 {
+                        self.write_sep(f, self.cell_separator)?;
+                    }
+                    self.write_cell(f, sudoku, [x, y])?;
+                }
+            }
+            return Ok(());
+        };
+        for y in 0..9 {
+            if y % 3 == 0 {
+                self.write_border(f, style, y / 3)?;
+                f.write_char('\n')?;
+            }
+            f.write_char(style.vertical())?;
+            for x in 0..9 {
+                self.write_sep(f, self.cell_separator)?;
+                self.write_cell(f, sudoku, [x, y])?;
+                if x % 3 == 2 {
+                    self.write_sep(f, self.cell_separator)?;
+                    f.write_char(style.vertical())?;
+                }
+            }
+            f.write_char('\n')?;
+        }
+        self.write_border(f, style, 3)
+    }
+
+    fn write_sep(&self, f: &mut impl fmt::Write, sep: Option<char>) -> fmt::Result {
+        sep.map_or(Ok(()), |sep| f.write_char(sep))
+    }
+
+    fn write_cell(&self, f: &mut impl fmt::Write, sudoku: &Sudoku, ix: [usize; 2]) -> fmt::Result {
+        let [x, y] = ix;
+        let cell = sudoku[ix];
+        let highlight = self.highlight[y][x];
+        if highlight {
+            f.write_str("\x1b[1m")?;
+        }
+        if cell.is_empty() {
+            f.write_char(self.empty)?;
+        } else {
+            write!(f, "{cell}")?;
+        }
+        if highlight {
+            f.write_str("\x1b[0m")?;
+        }
+        Ok(())
+    }
+
+    fn write_border(&self, f: &mut impl fmt::Write, style: BorderStyle, row: usize) -> fmt::Result {
+        let [left, inner, right] = style.junctions(row);
+        // Each box holds 3 cells with a separator before each of them and after the last one
+        let width = if self.cell_separator.is_some() { 7 } else { 3 };
+        f.write_char(left)?;
+        for b in 0..3 {
+            if b > 0 {
+                f.write_char(inner)?;
+            }
+            for _ in 0..width {
+                f.write_char(style.horizontal())?;
+            }
+        }
+        f.write_char(right)
+    }
+}
+
+/// A [`Sudoku`] formatted with a [`SudokuFormatter`]
+///
+/// Created by [`SudokuFormatter::display`].
+#[derive(Debug, Clone, Copy)]
+pub struct FormattedSudoku<'a> {
+    formatter: &'a SudokuFormatter,
+    sudoku: &'a Sudoku,
+}
+
+impl fmt::Display for FormattedSudoku<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.formatter.write(f, self.sudoku)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BorderStyle, SudokuFormatter};
+    use crate::solver::Sudoku;
+
+    const TEST_SUDOKU: &[u8; 81] =
+        b".......1.4.........2...........5.4.7..8...3....1.9....3..4..2...5.1........8.6...";
+
+    #[test]
+    fn format_grid() {
+        let sudoku = Sudoku::from_line(TEST_SUDOKU);
+        let expected = "\
++-------+-------+-------+
+|       |       |   1   |
+| 4     |       |       |
+|   2   |       |       |
++-------+-------+-------+
+|       |   5   | 4   7 |
+|     8 |       | 3     |
+|     1 |   9   |       |
++-------+-------+-------+
+| 3     | 4     | 2     |
+|   5   | 1     |       |
+|       | 8   6 |       |
++-------+-------+-------+";
+        assert_eq!(format!("{sudoku:#?}"), expected);
+    }
+
+    #[test]
+    fn format_custom() {
+        let sudoku = Sudoku::from_line(TEST_SUDOKU);
+        let formatter = SudokuFormatter::line()
+            .empty('0')
+            .cell_separator(Some(','))
+            .row_separator(Some(';'));
+        let formatted = formatter.display(&sudoku).to_string();
+        assert!(formatted.starts_with("0,0,0,0,0,0,0,1,0;4,0,"));
+        assert_eq!(formatted.len(), 81 + 8 * 9 + 8);
+
+        let formatter = SudokuFormatter::grid()
+            .cell_separator(None)
+            .borders(Some(BorderStyle::Unicode));
+        let formatted = formatter.display(&sudoku).to_string();
+        assert_eq!(formatted.lines().next(), Some("┌───┬───┬───┐"));
+        assert_eq!(formatted.lines().nth(1), Some("│   │   │ 1 │"));
+        assert_eq!(formatted.lines().last(), Some("└───┴───┴───┘"));
+    }
+
+    #[test]
+    fn format_highlight() {
+        let sudoku = Sudoku::from_line(TEST_SUDOKU);
+        let formatter = SudokuFormatter::line().highlight([0, 0]);
+        let formatted = formatter.display(&sudoku).to_string();
+        assert!(formatted.starts_with("\x1b[1m.\x1b[0m......1.4"));
+
+        let formatter = SudokuFormatter::line().highlight_filled(&sudoku);
+        let formatted = formatter.display(&sudoku).to_string();
+        assert!(formatted.starts_with(".......\x1b[1m1\x1b[0m.\x1b[1m4\x1b[0m...."));
+        assert_eq!(formatted.matches("\x1b[1m").count(), 17);
+        assert_eq!(formatted.matches("\x1b[0m").count(), 17);
+        let plain = formatted.replace("\x1b[1m", "").replace("\x1b[0m", "");
+        assert_eq!(plain.as_bytes(), TEST_SUDOKU);
+    }
+}
@@ -1 +1,7 @@
+//! A sudoku solver
+//!
+//! Most users only need the [`prelude`].
+pub mod error;
+pub mod formatter;
+pub mod prelude;
 pub mod solver;
@@ -1,40 +1,136 @@
 use std::{
-    io::{stdin, Read},
+    fs::File,
+    io::{stdin, stdout, BufWriter, Read, Write},
     ops::ControlFlow,
     process::ExitCode,
 };
 
-use libsolver::solver::{self, Solver, Sudoku};
+use libsolver::prelude::{IterativeDFS, SolveExt, Sudoku};
+
+mod repl;
 
 /// Program usage messaeg
 fn usage(prog: &str) -> String {
-    format!("Usage: {prog} [SOURCE]")
+    format!(
+        "Usage: {prog} [OPTIONS] [SOURCE]
+       {prog} repl
+
+Options:
+    -o, --output FILE   Write the solutions to FILE (`-` for stdout), compressed with gzip or zstd
+                        if FILE ends in `.gz` or `.zst`
+    -n, --line-numbers  Prefix each solution with the line number of its sudoku in SOURCE
+    --verify            Re-check every solution against its sudoku, abort on mismatch"
+    )
+}
+
+/// Parsed command line arguments
+struct Args {
+    src_path: String,
+    src: Box<[u8]>,
+    output: Option<String>,
+    line_numbers: bool,
+    verify: bool,
+}
+
+/// Where the solutions are written to
+enum Output {
+    Stdout(BufWriter<std::io::Stdout>),
+    Plain(BufWriter<File>),
+    Gzip(flate2::write::GzEncoder<BufWriter<File>>),
+    Zstd(zstd::Encoder<'static, BufWriter<File>>),
+}
+
+impl Output {
+    /// Create the output at `path`, picking the compression from its extension
+    fn create(path: &str) -> std::io::Result<Self> {
+        if path == "-" {
+            return Ok(Self::Stdout(BufWriter::new(stdout())));
+        }
+        let file = BufWriter::new(File::create(path)?);
+        let output = if path.ends_with(".gz") {
+            Self::Gzip(flate2::write::GzEncoder::new(
+                file,
+                flate2::Compression::default(),
+            ))
+        } else if path.ends_with(".zst") {
+            Self::Zstd(zstd::Encoder::new(file, 0)?)
+        } else {
+            Self::Plain(file)
+        };
+        Ok(output)
+    }
+
+    /// Flush all buffered data and write the compression trailer (if any)
+    fn finish(self) -> std::io::Result<()> {
+        match self {
+            Self::Stdout(mut out) => out.flush(),
+            Self::Plain(mut out) => out.flush(),
+            Self::Gzip(out) => out.finish()?.flush(),
+            Self::Zstd(out) => out.finish()?.flush(),
+        }
+    }
 }
 
-fn cli() -> ControlFlow<ExitCode, (String, Box<[u8]>)> {
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Stdout(out) => out.write(buf),
+            Self::Plain(out) => out.write(buf),
+            Self::Gzip(out) => out.write(buf),
+            Self::Zstd(out) => out.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Stdout(out) => out.flush(),
+            Self::Plain(out) => out.flush(),
+            Self::Gzip(out) => out.flush(),
+            Self::Zstd(out) => out.flush(),
+        }
+    }
+}
+
+fn cli() -> ControlFlow<ExitCode, Args> {
     let mut args = std::env::args();
     let Some(prog) = args.next() else {
         eprintln!("[ERROR]: No program name received through arguments");
         return ControlFlow::Break(ExitCode::FAILURE);
     };
-    let (Some(src_path), None) = (args.next(), args.next()) else {
-        eprintln!("[ERROR]: Invalid number of arguments provided, expected 1\n");
+    let mut src_path = None;
+    let mut output = None;
+    let mut line_numbers = false;
+    let mut verify = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" | "help" => {
+                println!("{}", usage(&prog));
+                return ControlFlow::Break(ExitCode::SUCCESS);
+            }
+            "-o" | "--output" => {
+                let Some(path) = args.next() else {
+                    eprintln!("[ERROR]: Missing FILE after {arg}\n");
+                    eprintln!("{}", usage(&prog));
+                    return ControlFlow::Break(ExitCode::FAILURE);
+                };
+                output = Some(path);
+            }
+            "-n" | "--line-numbers" => line_numbers = true,
+            "--verify" => verify = true,
+            _ if src_path.is_none() => src_path = Some(arg),
+            _ => {
+                eprintln!("[ERROR]: Invalid number of arguments provided, expected 1 SOURCE\n");
+                eprintln!("{}", usage(&prog));
+                return ControlFlow::Break(ExitCode::FAILURE);
+            }
+        }
+    }
+    let Some(src_path) = src_path else {
+        eprintln!("[ERROR]: No SOURCE provided\n");
         eprintln!("{}", usage(&prog));
         return ControlFlow::Break(ExitCode::FAILURE);
     };
     let src: Box<[u8]> = match src_path.as_str() {
-        "-h" => {
-            println!("{}", usage(&prog));
-            return ControlFlow::Break(ExitCode::SUCCESS);
-        }
-        "--help" => {
-            println!("{}", usage(&prog));
-            return ControlFlow::Break(ExitCode::SUCCESS);
-        }
-        "help" => {
-            println!("{}", usage(&prog));
-            return ControlFlow::Break(ExitCode::SUCCESS);
-        }
         "-" => {
             let mut stdin = stdin().lock();
             let mut v = vec![];
@@ -52,12 +148,33 @@ fn cli() -> ControlFlow<ExitCode, (String, Box<[u8]>)> {
             }
         },
     };
-    ControlFlow::Continue((src_path, src))
+    ControlFlow::Continue(Args {
+        src_path,
+        src,
+        output,
+        line_numbers,
+        verify,
+    })
 }
 
 fn main() -> ExitCode {
-    let (src_path, src) = match cli() {
-        ControlFlow::Continue(src) => src,
+    let mut args = std::env::args();
+    if let (Some(prog), Some("repl")) = (args.next(), args.next().as_deref()) {
+        if args.next().is_some() {
+            eprintln!("[ERROR]: repl does not take any arguments\n");
+            eprintln!("{}", usage(&prog));
+            return ExitCode::FAILURE;
+        }
+        return repl::run();
+    }
+    let Args {
+        src_path,
+        src,
+        output,
+        line_numbers,
+        verify,
+    } = match cli() {
+        ControlFlow::Continue(args) => args,
         ControlFlow::Break(code) => return code,
     };
 
@@ -76,17 +193,29 @@ fn main() -> ExitCode {
         1000f32 * start.elapsed().as_secs_f32()
     );
 
-    // Parse Sudokus
+    // Parse Sudokus, tagging each with the (1-based) line it was found on
     let start = std::time::Instant::now();
-    let sudokus: Vec<_> = contents
-        .split(u8::is_ascii_whitespace)
-        .filter(|s| !s.is_empty())
-        .map(|line| {
-            let s = Sudoku::from_line(line);
+    let sudokus: Vec<(usize, Sudoku)> = match contents
+        .split(|&b| b == b'\n')
+        .zip(1..)
+        .flat_map(|(line, line_no)| {
+            line.split(u8::is_ascii_whitespace)
+                .filter(|s| !s.is_empty())
+                .map(move |s| (line_no, s))
+        })
+        .map(|(line_no, line)| {
+            let s = Sudoku::try_from_line(line).map_err(|err| (line_no, err))?;
             debug_assert_eq!(line, format!("{s:?}").as_bytes());
-            s
+            Ok((line_no, s))
         })
-        .collect();
+        .collect()
+    {
+        Ok(sudokus) => sudokus,
+        Err((line_no, err)) => {
+            eprintln!("[ERROR]: failed to parse sudoku on line {line_no}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
     let count = sudokus.len();
     let parsing = start.elapsed();
     let total = total.elapsed();
@@ -100,15 +229,75 @@ fn main() -> ExitCode {
     );
     eprintln!("[INFO]: Total time {}s", total.as_secs_f32());
 
+    let mut output = match output.as_deref().map(Output::create).transpose() {
+        Ok(output) => output,
+        Err(err) => {
+            let path = output.as_deref().unwrap_or_default();
+            eprintln!("[ERROR]: failed to create output file {path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
     let start = std::time::Instant::now();
-    let _solved: Vec<_> = sudokus
-        .into_iter()
-        .enumerate()
-        .map(|(ix, sudoku)| {
-            eprint!("[INFO]: Solving {}/{count}\r", ix + 1);
-            solver::IterativeDFS.solve(sudoku)
-        })
-        .collect();
+    // Stop at the first failure, but always finish the output so the solutions written so far
+    // are not lost (a zstd stream is unreadable without its trailer)
+    let all_solved = 'solve: {
+        let solutions = sudokus
+            .iter()
+            .enumerate()
+            .map(|(ix, (_, sudoku))| {
+                eprint!("[INFO]: Solving {}/{count}\r", ix + 1);
+                sudoku.clone()
+            })
+            .solve_with(&IterativeDFS);
+        for ((line_no, givens), solved) in sudokus.iter().zip(solutions) {
+            let solved = match solved {
+                Ok(solved) => solved,
+                Err(err) => {
+                    // End the progress line
+                    eprintln!();
+                    eprintln!("[ERROR]: failed to solve sudoku on line {line_no}: {err}");
+                    let dead_ends = err
+                        .0
+                        .dead_ends()
+                        .map(|[x, y]| format!("r{}c{}", y + 1, x + 1))
+                        .collect::<Vec<_>>();
+                    if !dead_ends.is_empty() {
+                        eprintln!("        no candidates left for: {}", dead_ends.join(", "));
+                    }
+                    break 'solve false;
+                }
+            };
+            if verify && !solved.solves(givens) {
+                eprintln!();
+                eprintln!("[ERROR]: Verification failed for sudoku on line {line_no}");
+                eprintln!("        sudoku:   {givens:?}");
+                eprintln!("        solution: {:?}", Sudoku::from(solved));
+                break 'solve false;
+            }
+            if let Some(out) = output.as_mut() {
+                let solved = Sudoku::from(solved);
+                let written = if line_numbers {
+                    writeln!(out, "{line_no}:{solved:?}")
+                } else {
+                    writeln!(out, "{solved:?}")
+                };
+                if let Err(err) = written {
+                    eprintln!();
+                    eprintln!("[ERROR]: failed to write solution for line {line_no}: {err}");
+                    break 'solve false;
+                }
+            }
+        }
+        true
+    };
+    if let Some(Err(err)) = output.map(Output::finish) {
+        eprintln!("[ERROR]: failed to finish writing the solutions: {err}");
+        return ExitCode::FAILURE;
+    }
+    if !all_solved {
+        return ExitCode::FAILURE;
+    }
     let solving = start.elapsed().as_secs_f32();
     eprintln!(
         "[INFO]: Solved {count} sudokus in {solving:.3}s, that is {:.3}ms per sudoku",
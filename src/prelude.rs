@@ -0,0 +1,15 @@
+//! The commonly used types and traits of the crate
+//!
+//! ```
+//! use libsolver::prelude::*;
+//! ```
+#[cfg(feature = "rayon")]
+pub use crate::solver::ParSolveExt;
+pub use crate::{
+    error::{EmptySudokuCell, ExhaustedAllPossibilities, ParseSudokuError, UnsolvedSudoku},
+    formatter::{BorderStyle, SudokuFormatter},
+    solver::{
+        IterativeDFS, PartialSolution, SolveExt, SolvedSudoku, Solver, Sudoku, SudokuCell,
+        SudokuValue, SudokuValueSet,
+    },
+};
@@ -0,0 +1,136 @@
+//! Interactive mode: read commands from stdin, keeping a current grid between them
+use std::{
+    io::{stdin, stdout, BufRead, IsTerminal, Write},
+    process::ExitCode,
+};
+
+use libsolver::prelude::{IterativeDFS, SolvedSudoku, Solver, Sudoku, SudokuCell};
+
+/// REPL help message
+const HELP: &str = "Commands:
+    solve [SUDOKU]  Solve SUDOKU (81 characters, it becomes the current grid) or the current grid
+    hint            Fill in one cell of the current grid with its value in the solution
+    set rRcC=V      Set the cell at row R and column C (1-9) to V (1-9, or `.` to clear it)
+    show            Print the current grid
+    help            Print this message
+    quit            Exit the REPL";
+
+/// Run the REPL until `quit` or the end of stdin
+pub fn run() -> ExitCode {
+    let interactive = stdin().is_terminal();
+    let mut grid = Sudoku::from_line(&[b'.'; 81]);
+    let mut lines = stdin().lock().lines();
+    loop {
+        if interactive {
+            print!("> ");
+            // A missing prompt is not worth aborting over
+            let _ = stdout().flush();
+        }
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(err)) => {
+                eprintln!("[ERROR]: failed read from stdin: {err}");
+                return ExitCode::FAILURE;
+            }
+            None => return ExitCode::SUCCESS,
+        };
+        let mut words = line.split_whitespace();
+        let (Some(command), arg, None) = (words.next(), words.next(), words.next()) else {
+            if !line.trim().is_empty() {
+                eprintln!("[ERROR]: too many arguments in `{line}`, try `help`");
+            }
+            continue;
+        };
+        let result = match (command, arg) {
+            ("quit" | "exit", None) => return ExitCode::SUCCESS,
+            ("help", None) => {
+                println!("{HELP}");
+                Ok(())
+            }
+            ("show", None) => {
+                println!("{grid:#?}");
+                Ok(())
+            }
+            ("solve", sudoku) => solve(&mut grid, sudoku),
+            ("hint", None) => hint(&mut grid),
+            ("set", Some(assignment)) => set(&mut grid, assignment),
+            _ => Err(format!("invalid command `{line}`, try `help`")),
+        };
+        if let Err(err) = result {
+            eprintln!("[ERROR]: {err}");
+        }
+    }
+}
+
+/// Solve the current grid without modifying it
+fn solution(grid: &Sudoku) -> Result<SolvedSudoku, String> {
+    if !grid.valid() {
+        return Err("the current grid repeats a value in a row, column or box".to_string());
+    }
+    IterativeDFS
+        .try_solve(grid.clone())
+        .map_err(|err| err.to_string())
+}
+
+/// `solve [SUDOKU]`: print the solution of `sudoku` (or the current grid)
+fn solve(grid: &mut Sudoku, sudoku: Option<&str>) -> Result<(), String> {
+    if let Some(sudoku) = sudoku {
+        *grid = Sudoku::try_from_line(sudoku.as_bytes()).map_err(|err| err.to_string())?;
+    }
+    println!("{:?}", Sudoku::from(solution(grid)?));
+    Ok(())
+}
+
+/// `hint`: fill in the empty cell with the fewest candidates
+fn hint(grid: &mut Sudoku) -> Result<(), String> {
+    let solved = solution(grid)?;
+    let ix = grid
+        .indexed_values()
+        .filter(|(_, cell)| cell.is_empty())
+        .map(|(ix, _)| ix)
+        .min_by_key(|&ix| grid.candidates(ix).len())
+        .ok_or("the current grid is already solved")?;
+    grid[ix] = solved[ix].into();
+    let [x, y] = ix;
+    println!("r{}c{}={}", y + 1, x + 1, solved[ix]);
+    Ok(())
+}
+
+/// `set rRcC=V`: set (or clear) a cell of the current grid
+fn set(grid: &mut Sudoku, assignment: &str) -> Result<(), String> {
+    let invalid = || format!("invalid assignment `{assignment}`, expected rRcC=V (e.g. r4c7=3)");
+    let &[b'r', row @ b'1'..=b'9', b'c', column @ b'1'..=b'9', b'=', val] = assignment.as_bytes()
+    else {
+        return Err(invalid());
+    };
+    let cell = SudokuCell::from_ascii_char(val).ok_or_else(invalid)?;
+    grid[[usize::from(column - b'1'), usize::from(row - b'1')]] = cell;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use libsolver::solver::Sudoku;
+
+    use super::set;
+
+    #[test]
+    fn set_cell() {
+        let mut grid = Sudoku::from_line(&[b'.'; 81]);
+        set(&mut grid, "r4c7=3").unwrap();
+        assert_eq!(grid[[6, 3]].to_string(), "3");
+        assert_eq!(
+            grid.indexed_values().filter(|(_, c)| c.is_filled()).count(),
+            1
+        );
+        set(&mut grid, "r4c7=.").unwrap();
+        assert!(grid[[6, 3]].is_empty());
+
+        for invalid in [
+            "r0c1=1", "r1c0=1", "r10c1=1", "r1c1=0", "r1c1=", "c1r1=1", "r1c1=12",
+        ] {
+            assert!(set(&mut grid, invalid).is_err(), "accepted {invalid}");
+        }
+        assert!(grid.indexed_values().all(|(_, c)| c.is_empty()));
+    }
+}
@@ -1,11 +1,15 @@
-#![allow(dead_code)]
 use std::{
     num::NonZeroU8,
     ops::{Index, IndexMut},
 };
 
+use crate::{
+    error::{EmptySudokuCell, ExhaustedAllPossibilities, ParseSudokuError, UnsolvedSudoku},
+    formatter::SudokuFormatter,
+};
+
 pub trait Solver {
-    type Error: std::fmt::Debug;
+    type Error: std::error::Error;
 
     /// Solve a [`Sudoku`].
     ///
@@ -31,9 +35,6 @@ pub trait Solver {
 #[derive(Debug, Clone, Copy)]
 pub struct IterativeDFS;
 
-#[derive(Debug)]
-pub struct ExhaustedAllPossibilities(pub Sudoku);
-
 impl Solver for IterativeDFS {
     type Error = ExhaustedAllPossibilities;
 
@@ -56,7 +57,7 @@ impl Solver for IterativeDFS {
                 // Find the first value that is not contained in `all`
                 if let Some(val) = SudokuValue::all_values().find(|v| !all.contains(v)) {
                     // Save the state of the cell
-                    state.push((ix, val.into_iter()));
+                    state.push((ix, val.values_after()));
                     sudoku[ix] = SudokuCell::filled(val);
                     if all.len() < 8 {
                         // Sort by number of affecting values
@@ -84,7 +85,7 @@ impl Solver for IterativeDFS {
                 // From the values we have yet to try, find the first value which is also valid
                 if let Some(val) = values.find(|v| !all.contains(v)) {
                     // We found another candidate value, save current state and continue solving
-                    state.push((ix, val.into_iter()));
+                    state.push((ix, val.values_after()));
                     sudoku[ix] = SudokuCell::filled(val);
                     continue 'main;
                 }
@@ -94,48 +95,168 @@ impl Solver for IterativeDFS {
             }
             // We checked all values exhaustively. No more solutions are available (or we got the
             // implementation wrong).
-            return Err(ExhaustedAllPossibilities(sudoku));
+            return Err(ExhaustedAllPossibilities(PartialSolution::new(sudoku)));
         }
     }
 }
 
+/// Solve the [`Sudoku`]s of an iterator lazily
+///
+/// ```
+/// # use libsolver::solver::{IterativeDFS, SolveExt, Sudoku};
+/// let lines: &[&[u8]] = &[
+///     b".......1.4.........2...........5.4.7..8...3....1.9....3..4..2...5.1........8.6...",
+/// ];
+/// for solved in lines.iter().map(|line| Sudoku::from_line(line)).solve_with(&IterativeDFS) {
+///     println!("{}", solved.expect("the sudoku is solvable"));
+/// }
+/// ```
+pub trait SolveExt: Iterator<Item = Sudoku> + Sized {
+    /// Solve each [`Sudoku`] with `solver` as the iterator is consumed
+    fn solve_with<S: Solver>(self, solver: &S) -> SolveWith<'_, Self, S> {
+        SolveWith { iter: self, solver }
+    }
+}
+
+impl<I: Iterator<Item = Sudoku>> SolveExt for I {}
+
+/// An iterator that solves the [`Sudoku`]s of another iterator
+///
+/// Created by [`SolveExt::solve_with`].
+pub struct SolveWith<'a, I, S> {
+    iter: I,
+    solver: &'a S,
+}
+
+// Not derived: that would require `S: Clone`/`S: Debug`, but we only hold a reference to the solver
+impl<I: Clone, S> Clone for SolveWith<'_, I, S> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            solver: self.solver,
+        }
+    }
+}
+
+impl<I: std::fmt::Debug, S> std::fmt::Debug for SolveWith<'_, I, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SolveWith")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I: Iterator<Item = Sudoku>, S: Solver> Iterator for SolveWith<'_, I, S> {
+    type Item = Result<SolvedSudoku, S::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|sudoku| self.solver.try_solve(sudoku))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Solve the [`Sudoku`]s of a parallel iterator
+#[cfg(feature = "rayon")]
+pub trait ParSolveExt: rayon::iter::ParallelIterator<Item = Sudoku> {
+    /// Solve each [`Sudoku`] with `solver` on the rayon thread pool
+    fn par_solve_with<'a, S>(
+        self,
+        solver: &'a S,
+    ) -> impl rayon::iter::ParallelIterator<Item = Result<SolvedSudoku, S::Error>> + 'a
+    where
+        S: Solver + Sync,
+        S::Error: Send,
+        Self: 'a,
+    {
+        self.map(|sudoku| solver.try_solve(sudoku))
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<I: rayon::iter::ParallelIterator<Item = Sudoku>> ParSolveExt for I {}
+
+/// A [`Sudoku`] a [`Solver`] could not finish, with the candidates left for each cell
 #[derive(Debug, Clone)]
-struct SudokuValueSet([bool; 9]);
+pub struct PartialSolution {
+    sudoku: Sudoku,
+    candidates: [[SudokuValueSet; 9]; 9],
+}
 
-impl SudokuValueSet {
-    pub fn new() -> Self {
-        Self([false; 9])
+impl PartialSolution {
+    /// Compute the candidates of every empty cell of `sudoku` (filled cells have none)
+    pub fn new(sudoku: Sudoku) -> Self {
+        let mut candidates = [[SudokuValueSet::default(); 9]; 9];
+        for (ix, cell) in sudoku.indexed_values() {
+            if cell.is_empty() {
+                let [x, y] = ix;
+                candidates[y][x] = sudoku.candidates(ix);
+            }
+        }
+        Self { sudoku, candidates }
+    }
+
+    /// The (partially filled) [`Sudoku`]
+    pub fn sudoku(&self) -> &Sudoku {
+        &self.sudoku
+    }
+
+    /// Take the (partially filled) [`Sudoku`]
+    pub fn into_sudoku(self) -> Sudoku {
+        self.sudoku
     }
 
-    pub fn insert(&mut self, val: SudokuValue) -> bool {
+    /// The values that can still go in the cell at `ix`
+    pub fn candidates(&self, ix: impl Into<[usize; 2]>) -> &SudokuValueSet {
+        let [x, y] = ix.into();
+        &self.candidates[y][x]
+    }
+
+    /// The empty cells that have no candidates left; these make the [`Sudoku`] unsolvable
+    pub fn dead_ends(&self) -> impl Iterator<Item = [usize; 2]> + '_ {
+        self.sudoku
+            .indexed_values()
+            .filter(|&(ix, cell)| cell.is_empty() && self.candidates(ix).is_empty())
+            .map(|(ix, _)| ix)
+    }
+}
+
+/// A set of [`SudokuValue`]s
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SudokuValueSet(u16);
+
+impl SudokuValueSet {
+    pub(crate) fn insert(&mut self, val: SudokuValue) -> bool {
         debug_assert!((1..=9).contains(&val.0.get()));
-        let ix = usize::from(val.0.get()) - 1;
-        let prev = self.0[ix];
-        self.0[ix] = true;
-        !prev
+        let prev = self.0 & (1 << val.0.get());
+        self.0 |= 1 << val.0.get();
+        prev == 0
     }
 
     pub fn contains(&self, val: &SudokuValue) -> bool {
         debug_assert!((1..=9).contains(&val.0.get()));
-        let ix = usize::from(val.0.get()) - 1;
-        self.0[ix]
+        self.0 & (1 << val.0.get()) != 0
     }
 
     pub fn len(&self) -> usize {
-        self.0.iter().copied().filter(|&v| v).count()
+        self.0.count_ones() as usize
     }
-}
 
-impl Extend<SudokuValue> for SudokuValueSet {
-    fn extend<T: IntoIterator<Item = SudokuValue>>(&mut self, iter: T) {
-        for val in iter {
-            self.insert(val);
-        }
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The values in the set, in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = SudokuValue> + '_ {
+        SudokuValue::all_values().filter(|val| self.contains(val))
     }
 }
 
+/// Iterator over the [`SudokuValue`]s after a given value (in ascending order)
 #[derive(Debug, Clone)]
-pub struct SudokuValues(u8);
+pub(crate) struct SudokuValues(u8);
 
 impl Iterator for SudokuValues {
     type Item = SudokuValue;
@@ -154,6 +275,8 @@ impl Iterator for SudokuValues {
     }
 }
 
+impl ExactSizeIterator for SudokuValues {}
+
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SudokuValue(NonZeroU8);
@@ -168,29 +291,22 @@ impl SudokuValue {
     /// # Safety
     ///
     /// Must be a number in the 1..=9 range
-    pub unsafe fn new_unchecked(val: u8) -> Self {
+    pub(crate) unsafe fn new_unchecked(val: u8) -> Self {
         debug_assert!((1..=9).contains(&val));
         SudokuValue(NonZeroU8::new_unchecked(val))
     }
 
-    pub fn all_values() -> SudokuValues {
+    /// All values from 1 to 9 (in ascending order)
+    pub fn all_values() -> impl ExactSizeIterator<Item = SudokuValue> + Clone {
         SudokuValues(0)
     }
-}
-
-impl IntoIterator for SudokuValue {
-    type Item = SudokuValue;
-
-    type IntoIter = SudokuValues;
 
-    fn into_iter(self) -> Self::IntoIter {
+    /// The values after this one (in ascending order)
+    pub(crate) fn values_after(self) -> SudokuValues {
         SudokuValues(self.0.get())
     }
 }
 
-#[derive(Debug)]
-pub struct EmptySudokuCell;
-
 impl TryFrom<SudokuCell> for SudokuValue {
     type Error = EmptySudokuCell;
 
@@ -226,7 +342,7 @@ impl SudokuCell {
         self.0.is_none()
     }
 
-    pub fn from_ascci_char(val: u8) -> Option<Self> {
+    pub fn from_ascii_char(val: u8) -> Option<Self> {
         if val == b'.' {
             Some(Self::empty())
         } else {
@@ -245,7 +361,7 @@ impl From<SolvedSudoku> for Sudoku {
 }
 
 impl TryFrom<Sudoku> for SolvedSudoku {
-    type Error = ();
+    type Error = UnsolvedSudoku;
 
     fn try_from(value: Sudoku) -> Result<Self, Self::Error> {
         value
@@ -253,7 +369,20 @@ impl TryFrom<Sudoku> for SolvedSudoku {
             .then_some(Self(value.0.map(|r| {
                 r.map(|c| SudokuValue::try_from(c).expect("a solved Sudoku has no empty cells"))
             })))
-            .ok_or(())
+            .ok_or(UnsolvedSudoku)
+    }
+}
+
+impl SolvedSudoku {
+    /// Check that this is a solution to `sudoku`.
+    ///
+    /// The grid is re-validated from scratch (no empty cells, no repeated values in any row,
+    /// column or box) and every filled cell of `sudoku` must hold the same value here.
+    pub fn solves(&self, sudoku: &Sudoku) -> bool {
+        Sudoku::from(self.clone()).solved()
+            && sudoku
+                .indexed_values()
+                .all(|(ix, cell)| cell.0.map_or(true, |val| val == self[ix]))
     }
 }
 
@@ -266,77 +395,68 @@ impl<Ix: Into<[usize; 2]>> Index<Ix> for SolvedSudoku {
     }
 }
 
-impl<Ix: Into<[usize; 2]>> IndexMut<Ix> for SolvedSudoku {
-    fn index_mut(&mut self, ix: Ix) -> &mut Self::Output {
-        let [x, y] = ix.into();
-        &mut self.0[y][x]
-    }
-}
-
-pub struct Column<'a> {
-    sudoku: &'a Sudoku,
-    x: u8,
-    y: u8,
-}
-
-impl<'a> Iterator for Column<'a> {
-    type Item = &'a SudokuCell;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.y >= 9 {
-            return None;
+/// Cells (as row-major indexes) that make up each unit: the 9 rows, then the 9 columns and then
+/// the 9 boxes
+static UNITS: [[u8; 9]; 27] = {
+    let mut units = [[0; 9]; 27];
+    let mut unit = 0;
+    while unit < 9 {
+        let mut ix = 0;
+        while ix < 9 {
+            units[unit][ix] = (9 * unit + ix) as u8;
+            units[9 + unit][ix] = (unit + 9 * ix) as u8;
+            units[18 + unit][ix] = (27 * (unit / 3) + 3 * (unit % 3) + 9 * (ix / 3) + ix % 3) as u8;
+            ix += 1;
         }
-        let ix = [self.x, self.y].map(Into::into);
-        self.y += 1;
-        Some(&self.sudoku[ix])
+        unit += 1;
     }
+    units
+};
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let left = 9usize.saturating_sub(self.y.into());
-        (left, Some(left))
+/// The 20 cells (as row-major indexes) that share a row, column or box with each cell
+static PEERS: [[u8; 20]; 81] = {
+    let mut peers = [[0; 20]; 81];
+    let mut cell = 0;
+    while cell < 81 {
+        let (x, y) = (cell % 9, cell / 9);
+        let mut count = 0;
+        let mut peer = 0;
+        while peer < 81 {
+            let (px, py) = (peer % 9, peer / 9);
+            let same_box = x / 3 == px / 3 && y / 3 == py / 3;
+            if peer != cell && (x == px || y == py || same_box) {
+                peers[cell][count] = peer as u8;
+                count += 1;
+            }
+            peer += 1;
+        }
+        assert!(count == 20);
+        cell += 1;
     }
-}
+    peers
+};
 
-pub struct Row<'a> {
+/// An iterator over the cells of a row, column or box
+#[derive(Debug, Clone)]
+pub struct Unit<'a> {
     sudoku: &'a Sudoku,
-    x: u8,
-    y: u8,
+    cells: std::slice::Iter<'static, u8>,
 }
 
-impl<'a> Iterator for Row<'a> {
+impl<'a> Iterator for Unit<'a> {
     type Item = &'a SudokuCell;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.x >= 9 {
-            return None;
-        }
-        let ix = [self.x, self.y].map(Into::into);
-        self.x += 1;
-        Some(&self.sudoku[ix])
+        self.cells.next().map(|&ix| self.sudoku.flat(ix))
     }
-}
-
-pub struct Cell<'a> {
-    sudoku: &'a Sudoku,
-    pos: u8,
-    ix: u8,
-}
 
-impl<'a> Iterator for Cell<'a> {
-    type Item = &'a SudokuCell;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.ix >= 9 {
-            return None;
-        }
-        let (x, y) = (self.pos % 3, self.pos / 3);
-        let (x_off, y_off) = (self.ix % 3, self.ix / 3);
-        let ix = [3 * x + x_off, 3 * y + y_off].map(Into::into);
-        self.ix += 1;
-        Some(&self.sudoku[ix])
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
     }
 }
 
+impl ExactSizeIterator for Unit<'_> {}
+
 #[derive(Clone)]
 pub struct Sudoku([[SudokuCell; 9]; 9]);
 
@@ -354,37 +474,73 @@ fn unique<'a>(values: impl IntoIterator<Item = &'a SudokuCell>) -> bool {
 }
 
 impl Sudoku {
+    /// Parse a [`Sudoku`] from a line of 81 ascii characters
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the line is not a valid [`Sudoku`]. If you want to catch this
+    /// error, use [`try_from_line`] instead.
+    ///
+    /// [`try_from_line`]: Sudoku::try_from_line
     pub fn from_line(line: &[u8]) -> Self {
-        assert_eq!(line.len(), 81);
+        Self::try_from_line(line).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Parse a [`Sudoku`] from a line of 81 ascii characters
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the line is not 81 characters long, or if any
+    /// character is not `.` or a digit in `1..=9`.
+    pub fn try_from_line(line: &[u8]) -> Result<Self, ParseSudokuError> {
+        if line.len() != 81 {
+            return Err(ParseSudokuError::InvalidLength(line.len()));
+        }
         let mut sudoku = [[SudokuCell::empty(); 9]; 9];
-        for (b, val) in line.iter().copied().zip(sudoku.iter_mut().flatten()) {
-            if let Some(v) = SudokuCell::from_ascci_char(b) {
-                *val = v;
-            } else {
-                panic!(
-                    "bad SudokuValue: b'{}' expected '.' or [1-9]",
-                    b.escape_ascii()
-                )
-            }
+        let cells = line.iter().copied().zip(sudoku.iter_mut().flatten());
+        for (ix, (b, val)) in cells.enumerate() {
+            *val = SudokuCell::from_ascii_char(b)
+                .ok_or(ParseSudokuError::InvalidChar { ix, byte: b })?;
         }
-        Self(sudoku)
+        Ok(Self(sudoku))
     }
+
     // All values that affect the cell at `ix`
     fn all_affecting(&self, ix: [usize; 2]) -> SudokuValueSet {
-        let row = self
-            .row(Sudoku::row_from_ix(ix))
-            .filter_map(|cell| SudokuValue::try_from(*cell).ok());
-        let column = self
-            .column(Sudoku::column_from_ix(ix))
-            .filter_map(|cell| SudokuValue::try_from(*cell).ok());
-        let cell = self
-            .cell(Sudoku::cell_from_ix(ix))
-            .filter_map(|cell| SudokuValue::try_from(*cell).ok());
-        let mut all = SudokuValueSet::new();
-        all.extend(row);
-        all.extend(column);
-        all.extend(cell);
-        all
+        let [x, y] = ix;
+        let mut bits = 0;
+        for &peer in &PEERS[9 * y + x] {
+            // Empty cells set bit 0, which is not a value
+            bits |= 1 << self.flat(peer).0.map_or(0, |val| val.0.get());
+        }
+        SudokuValueSet(bits & !1)
+    }
+
+    /// The values that can go in the (empty) cell at `ix` without repeating a value in its row,
+    /// column or box
+    pub fn candidates(&self, ix: impl Into<[usize; 2]>) -> SudokuValueSet {
+        let all = self.all_affecting(ix.into());
+        let mut candidates = SudokuValueSet::default();
+        for val in SudokuValue::all_values().filter(|val| !all.contains(val)) {
+            candidates.insert(val);
+        }
+        candidates
+    }
+
+    // The cell at row-major index `ix`
+    fn flat(&self, ix: u8) -> &SudokuCell {
+        // `[[T; 9]; 9]` has the same layout as `[T; 81]`; this is `as_flattened` (Rust 1.80), which
+        // is much faster than indexing with `ix / 9` and `ix % 9` in the solver loop
+        let cells: &[SudokuCell; 81] = unsafe { &*self.0.as_ptr().cast() };
+        &cells[usize::from(ix)]
+    }
+
+    // The cells of the unit at `ix` (see [`UNITS`])
+    fn unit(&self, ix: usize) -> Unit<'_> {
+        Unit {
+            sudoku: self,
+            cells: UNITS[ix].iter(),
+        }
     }
 
     pub fn filled(&self) -> bool {
@@ -392,7 +548,7 @@ impl Sudoku {
     }
 
     pub fn valid(&self) -> bool {
-        (0..9u8).all(|ix| unique(self.row(ix)) && unique(self.column(ix)) && unique(self.cell(ix)))
+        (0..UNITS.len()).all(|ix| unique(self.unit(ix)))
     }
 
     pub fn solved(&self) -> bool {
@@ -411,46 +567,19 @@ impl Sudoku {
             .map(|(ix, cell)| ([ix % 9, ix / 9], cell))
     }
 
-    pub fn cell(&self, ix: u8) -> Cell<'_> {
+    pub fn cell(&self, ix: u8) -> Unit<'_> {
         assert!(ix < 9);
-        Cell {
-            sudoku: self,
-            pos: ix,
-            ix: 0,
-        }
+        self.unit(18 + usize::from(ix))
     }
 
-    pub fn row(&self, ix: u8) -> Row<'_> {
+    pub fn row(&self, ix: u8) -> Unit<'_> {
         assert!(ix < 9);
-        Row {
-            sudoku: self,
-            x: 0,
-            y: ix,
-        }
+        self.unit(usize::from(ix))
     }
 
-    pub fn column(&self, ix: u8) -> Column<'_> {
+    pub fn column(&self, ix: u8) -> Unit<'_> {
         assert!(ix < 9);
-        Column {
-            sudoku: self,
-            x: ix,
-            y: 0,
-        }
-    }
-
-    pub fn row_from_ix(ix: [usize; 2]) -> u8 {
-        let [_x, y] = ix;
-        y as u8
-    }
-
-    pub fn column_from_ix(ix: [usize; 2]) -> u8 {
-        let [x, _y] = ix;
-        x as u8
-    }
-
-    pub fn cell_from_ix(ix: [usize; 2]) -> u8 {
-        let [x, y] = ix;
-        (3 * (y / 3) + x / 3) as u8
+        self.unit(9 + usize::from(ix))
     }
 }
 
@@ -497,204 +626,26 @@ impl std::fmt::Display for SudokuCell {
 
 impl std::fmt::Display for SolvedSudoku {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s: Sudoku = self.clone().into();
-        write!(f, "{s:#?}")
+        SudokuFormatter::grid().write(f, &self.clone().into())
     }
 }
 
 impl std::fmt::Debug for Sudoku {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if f.alternate() {
-            // Pretty print:
-            write!(
-                f,
-                "+-------+-------+-------+
-| {:#} {:#} {:#} | {:#} {:#} {:#} | {:#} {:#} {:#} |
-| {:#} {:#} {:#} | {:#} {:#} {:#} | {:#} {:#} {:#} |
-| {:#} {:#} {:#} | {:#} {:#} {:#} | {:#} {:#} {:#} |
-+-------+-------+-------+
-| {:#} {:#} {:#} | {:#} {:#} {:#} | {:#} {:#} {:#} |
-| {:#} {:#} {:#} | {:#} {:#} {:#} | {:#} {:#} {:#} |
-| {:#} {:#} {:#} | {:#} {:#} {:#} | {:#} {:#} {:#} |
-+-------+-------+-------+
-| {:#} {:#} {:#} | {:#} {:#} {:#} | {:#} {:#} {:#} |
-| {:#} {:#} {:#} | {:#} {:#} {:#} | {:#} {:#} {:#} |
-| {:#} {:#} {:#} | {:#} {:#} {:#} | {:#} {:#} {:#} |
-+-------+-------+-------+",
-                self[(0, 0)],
-                self[(1, 0)],
-                self[(2, 0)],
-                self[(3, 0)],
-                self[(4, 0)],
-                self[(5, 0)],
-                self[(6, 0)],
-                self[(7, 0)],
-                self[(8, 0)],
-                self[(0, 1)],
-                self[(1, 1)],
-                self[(2, 1)],
-                self[(3, 1)],
-                self[(4, 1)],
-                self[(5, 1)],
-                self[(6, 1)],
-                self[(7, 1)],
-                self[(8, 1)],
-                self[(0, 2)],
-                self[(1, 2)],
-                self[(2, 2)],
-                self[(3, 2)],
-                self[(4, 2)],
-                self[(5, 2)],
-                self[(6, 2)],
-                self[(7, 2)],
-                self[(8, 2)],
-                self[(0, 3)],
-                self[(1, 3)],
-                self[(2, 3)],
-                self[(3, 3)],
-                self[(4, 3)],
-                self[(5, 3)],
-                self[(6, 3)],
-                self[(7, 3)],
-                self[(8, 3)],
-                self[(0, 4)],
-                self[(1, 4)],
-                self[(2, 4)],
-                self[(3, 4)],
-                self[(4, 4)],
-                self[(5, 4)],
-                self[(6, 4)],
-                self[(7, 4)],
-                self[(8, 4)],
-                self[(0, 5)],
-                self[(1, 5)],
-                self[(2, 5)],
-                self[(3, 5)],
-                self[(4, 5)],
-                self[(5, 5)],
-                self[(6, 5)],
-                self[(7, 5)],
-                self[(8, 5)],
-                self[(0, 6)],
-                self[(1, 6)],
-                self[(2, 6)],
-                self[(3, 6)],
-                self[(4, 6)],
-                self[(5, 6)],
-                self[(6, 6)],
-                self[(7, 6)],
-                self[(8, 6)],
-                self[(0, 7)],
-                self[(1, 7)],
-                self[(2, 7)],
-                self[(3, 7)],
-                self[(4, 7)],
-                self[(5, 7)],
-                self[(6, 7)],
-                self[(7, 7)],
-                self[(8, 7)],
-                self[(0, 8)],
-                self[(1, 8)],
-                self[(2, 8)],
-                self[(3, 8)],
-                self[(4, 8)],
-                self[(5, 8)],
-                self[(6, 8)],
-                self[(7, 8)],
-                self[(8, 8)],
-            )
+            SudokuFormatter::grid().write(f, self)
         } else {
-            write!(
-                f, "{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}",
-                self[(0, 0)],
-                self[(1, 0)],
-                self[(2, 0)],
-                self[(3, 0)],
-                self[(4, 0)],
-                self[(5, 0)],
-                self[(6, 0)],
-                self[(7, 0)],
-                self[(8, 0)],
-                self[(0, 1)],
-                self[(1, 1)],
-                self[(2, 1)],
-                self[(3, 1)],
-                self[(4, 1)],
-                self[(5, 1)],
-                self[(6, 1)],
-                self[(7, 1)],
-                self[(8, 1)],
-                self[(0, 2)],
-                self[(1, 2)],
-                self[(2, 2)],
-                self[(3, 2)],
-                self[(4, 2)],
-                self[(5, 2)],
-                self[(6, 2)],
-                self[(7, 2)],
-                self[(8, 2)],
-                self[(0, 3)],
-                self[(1, 3)],
-                self[(2, 3)],
-                self[(3, 3)],
-                self[(4, 3)],
-                self[(5, 3)],
-                self[(6, 3)],
-                self[(7, 3)],
-                self[(8, 3)],
-                self[(0, 4)],
-                self[(1, 4)],
-                self[(2, 4)],
-                self[(3, 4)],
-                self[(4, 4)],
-                self[(5, 4)],
-                self[(6, 4)],
-                self[(7, 4)],
-                self[(8, 4)],
-                self[(0, 5)],
-                self[(1, 5)],
-                self[(2, 5)],
-                self[(3, 5)],
-                self[(4, 5)],
-                self[(5, 5)],
-                self[(6, 5)],
-                self[(7, 5)],
-                self[(8, 5)],
-                self[(0, 6)],
-                self[(1, 6)],
-                self[(2, 6)],
-                self[(3, 6)],
-                self[(4, 6)],
-                self[(5, 6)],
-                self[(6, 6)],
-                self[(7, 6)],
-                self[(8, 6)],
-                self[(0, 7)],
-                self[(1, 7)],
-                self[(2, 7)],
-                self[(3, 7)],
-                self[(4, 7)],
-                self[(5, 7)],
-                self[(6, 7)],
-                self[(7, 7)],
-                self[(8, 7)],
-                self[(0, 8)],
-                self[(1, 8)],
-                self[(2, 8)],
-                self[(3, 8)],
-                self[(4, 8)],
-                self[(5, 8)],
-                self[(6, 8)],
-                self[(7, 8)],
-                self[(8, 8)],
-            )
+            SudokuFormatter::line().write(f, self)
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{IterativeDFS, Solver, Sudoku};
+    use super::{IterativeDFS, PartialSolution, SolveExt, Solver, Sudoku, PEERS};
+    use crate::error::{
+        EmptySudokuCell, ExhaustedAllPossibilities, ParseSudokuError, UnsolvedSudoku,
+    };
 
     const TEST_SUDOKU: &[u8; 81] =
         b".......1.4.........2...........5.4.7..8...3....1.9....3..4..2...5.1........8.6...";
@@ -721,10 +672,129 @@ mod test {
         }
     }
 
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Sudoku::try_from_line(&TEST_SUDOKU[1..]).err(),
+            Some(ParseSudokuError::InvalidLength(80))
+        );
+        let mut line = *TEST_SUDOKU;
+        line[3] = b'0';
+        assert_eq!(
+            Sudoku::try_from_line(&line).err(),
+            Some(ParseSudokuError::InvalidChar { ix: 3, byte: b'0' })
+        );
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(
+            ParseSudokuError::InvalidLength(80).to_string(),
+            "expected 81 cells, found 80"
+        );
+        assert_eq!(
+            ParseSudokuError::InvalidChar { ix: 3, byte: b'0' }.to_string(),
+            "invalid character '0' at cell 3, expected '.' or a digit in 1-9"
+        );
+        assert_eq!(
+            ParseSudokuError::InvalidChar { ix: 0, byte: b'\n' }.to_string(),
+            "invalid character '\\n' at cell 0, expected '.' or a digit in 1-9"
+        );
+        assert_eq!(EmptySudokuCell.to_string(), "the sudoku cell is empty");
+        assert_eq!(UnsolvedSudoku.to_string(), "the sudoku is not solved");
+        let exhausted = |line: &[u8]| {
+            ExhaustedAllPossibilities(PartialSolution::new(Sudoku::from_line(line))).to_string()
+        };
+        assert_eq!(
+            exhausted(TEST_SUDOKU),
+            "exhausted all possibilities without finding a solution"
+        );
+        let mut line = [b'.'; 81];
+        line[..9].copy_from_slice(b".12345678");
+        line[9] = b'9';
+        assert_eq!(
+            exhausted(&line),
+            "exhausted all possibilities without finding a solution (1 cell has no candidates)"
+        );
+        line[72..].copy_from_slice(b"12345678.");
+        line[71] = b'9';
+        assert_eq!(
+            exhausted(&line),
+            "exhausted all possibilities without finding a solution (2 cells have no candidates)"
+        );
+    }
+
+    #[test]
+    fn partial_solution() {
+        let mut line = [b'.'; 81];
+        line[..9].copy_from_slice(b".12345678");
+        line[9] = b'9';
+        let sudoku = Sudoku::from_line(&line);
+        assert!(sudoku.valid());
+        let Err(ExhaustedAllPossibilities(partial)) = IterativeDFS.try_solve(sudoku) else {
+            panic!("solved an unsolvable sudoku");
+        };
+        assert_eq!(partial.dead_ends().collect::<Vec<_>>(), [[0, 0]]);
+        assert_eq!(partial.candidates([0, 0]).len(), 0);
+        assert_eq!(partial.candidates([1, 1]).len(), 6);
+        assert_eq!(partial.candidates([1, 0]).len(), 0);
+    }
+
+    #[test]
+    fn peer_tables() {
+        // (row, column, box) of the cell at row-major index `ix`
+        let units = |ix: usize| (ix / 9, ix % 9, 3 * (ix / 27) + ix % 9 / 3);
+        for (ix, peers) in PEERS.iter().enumerate() {
+            let (row, column, cell) = units(ix);
+            for (n, &peer) in peers.iter().enumerate() {
+                assert!(!peers[n + 1..].contains(&peer));
+                assert_ne!(ix, usize::from(peer));
+                let (peer_row, peer_column, peer_cell) = units(usize::from(peer));
+                assert!(peer_row == row || peer_column == column || peer_cell == cell);
+            }
+        }
+    }
+
     #[test]
     fn solve_sudoku_iterative_dfs() {
         let sudoku = Sudoku::from_line(TEST_SUDOKU);
         let solver = IterativeDFS;
         solver.solve(sudoku);
     }
+
+    #[test]
+    fn solve_iterator() {
+        let sudokus = TEST_SUDOKUS[..2]
+            .iter()
+            .map(|line| Sudoku::from_line(*line));
+        let solved = sudokus.clone().solve_with(&IterativeDFS);
+        assert_eq!(solved.size_hint(), (2, Some(2)));
+        for (sudoku, solved) in sudokus.zip(solved) {
+            assert!(solved.unwrap().solves(&sudoku));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_solve_iterator() {
+        use super::ParSolveExt;
+        use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+        let solved: Vec<_> = TEST_SUDOKUS[..2]
+            .par_iter()
+            .map(|line| Sudoku::from_line(*line))
+            .par_solve_with(&IterativeDFS)
+            .collect();
+        for (&line, solved) in TEST_SUDOKUS.iter().zip(solved) {
+            assert!(solved.unwrap().solves(&Sudoku::from_line(line)));
+        }
+    }
+
+    #[test]
+    fn verify_solution() {
+        let sudoku = Sudoku::from_line(TEST_SUDOKU);
+        let solved = IterativeDFS.solve(sudoku.clone());
+        assert!(solved.solves(&sudoku));
+        assert!(!solved.solves(&Sudoku::from_line(TEST_SUDOKUS[1])));
+    }
 }
@@ -0,0 +1,76 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const TEST_SUDOKU: &str =
+    ".......1.4.........2...........5.4.7..8...3....1.9....3..4..2...5.1........8.6...";
+const TEST_SOLUTION: &str =
+    "693784512487512936125963874932651487568247391741398625319475268856129743274836159";
+/// No value fits in the first cell: its row holds 1-8 and its column a 9
+const UNSOLVABLE_SUDOKU: &str =
+    ".123456789.......................................................................";
+
+/// A path in the temp dir unique to this test process
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sudoku-solver-{}-{name}", std::process::id()))
+}
+
+/// Run the solver on `input`, writing the solutions to `output`; returns whether it succeeded
+fn solve(input: &str, output: &Path) -> bool {
+    let src = temp_path(&format!(
+        "{}.in",
+        output.file_name().unwrap().to_string_lossy()
+    ));
+    std::fs::write(&src, input).unwrap();
+    let status = Command::new(env!("CARGO_BIN_EXE_sudoku-solver"))
+        .arg("-o")
+        .arg(output)
+        .arg(&src)
+        .output()
+        .unwrap()
+        .status;
+    std::fs::remove_file(src).unwrap();
+    status.success()
+}
+
+/// Decompress the solver output at `path` based on its extension
+fn read_output(path: &Path) -> String {
+    let file = std::fs::File::open(path).unwrap();
+    let mut contents = String::new();
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("gz") => flate2::read::GzDecoder::new(file).read_to_string(&mut contents),
+        Some("zst") => zstd::Decoder::new(file)
+            .unwrap()
+            .read_to_string(&mut contents),
+        _ => panic!("unexpected output file {}", path.display()),
+    }
+    .unwrap();
+    std::fs::remove_file(path).unwrap();
+    contents
+}
+
+#[test]
+fn compressed_output() {
+    for ext in ["gz", "zst"] {
+        let output = temp_path(&format!("solutions.txt.{ext}"));
+        assert!(solve(&format!("{TEST_SUDOKU}\n{TEST_SUDOKU}\n"), &output));
+        assert_eq!(
+            read_output(&output),
+            format!("{TEST_SOLUTION}\n{TEST_SOLUTION}\n")
+        );
+    }
+}
+
+#[test]
+fn compressed_output_is_finished_on_failure() {
+    for ext in ["gz", "zst"] {
+        let output = temp_path(&format!("partial.txt.{ext}"));
+        assert!(!solve(
+            &format!("{TEST_SUDOKU}\n{UNSOLVABLE_SUDOKU}\n"),
+            &output
+        ));
+        assert_eq!(read_output(&output), format!("{TEST_SOLUTION}\n"));
+    }
+}